@@ -8,6 +8,93 @@ pub enum CurveError {
     OutOfRange,
     ZeroInput,
     ExceedsPool,
+    SlippageExceeded,
+}
+
+/// Serde support for the curve types, gated behind the optional `serde`
+/// feature so a caller who never needs persistence doesn't pay for the
+/// dependency. Following CoW Protocol's `HexOrDecimalU256`, every u128 field
+/// accepts either a `"0x…"` hex string or a plain decimal string on the way
+/// in, and always serializes back out as a decimal string so the wire
+/// format stays plain JSON, matching the convention the WASM boundary
+/// already uses.
+#[cfg(feature = "serde")]
+mod serde_support {
+    fn parse_u128(raw: &str) -> Result<u128, std::num::ParseIntError> {
+        match raw.strip_prefix("0x").or_else(|| raw.strip_prefix("0X")) {
+            Some(hex) => u128::from_str_radix(hex, 16),
+            None => raw.parse::<u128>(),
+        }
+    }
+
+    /// `#[serde(with = "...")]` module for a plain `u128` field.
+    pub mod hex_or_decimal_u128 {
+        use serde::{Deserialize, Deserializer, Serializer};
+
+        pub fn serialize<S>(value: &u128, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            serializer.serialize_str(&value.to_string())
+        }
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<u128, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let raw = String::deserialize(deserializer)?;
+            super::parse_u128(&raw).map_err(serde::de::Error::custom)
+        }
+    }
+
+    /// `#[serde(with = "...")]` module for an `Option<u128>` field. `None`
+    /// round-trips as JSON `null`.
+    pub mod hex_or_decimal_u128_opt {
+        use serde::{Deserialize, Deserializer, Serializer};
+
+        pub fn serialize<S>(value: &Option<u128>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            match value {
+                Some(v) => serializer.serialize_str(&v.to_string()),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<u128>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let raw: Option<String> = Option::deserialize(deserializer)?;
+            raw.map(|s| super::parse_u128(&s).map_err(serde::de::Error::custom))
+                .transpose()
+        }
+    }
+}
+
+/// Pricing shape selected at construction time. All three shapes sit behind
+/// the same `mint`/`burn`/`snapshot` API, so a WASM caller picks a shape
+/// once and nothing else about the curve changes.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CurveKind {
+    /// Today's formula: `X = k / Y` with virtual token reserves.
+    ConstantProduct,
+    /// Fixed price per token, independent of step: `sats_out = tokens * price_num / price_den`.
+    Flat {
+        #[cfg_attr(feature = "serde", serde(with = "serde_support::hex_or_decimal_u128"))]
+        price_num: u128,
+        #[cfg_attr(feature = "serde", serde(with = "serde_support::hex_or_decimal_u128"))]
+        price_den: u128,
+    },
+    /// Spot price proportional to tokens sold: `p(step) = (m_num/m_den) * step`.
+    Linear {
+        #[cfg_attr(feature = "serde", serde(with = "serde_support::hex_or_decimal_u128"))]
+        m_num: u128,
+        #[cfg_attr(feature = "serde", serde(with = "serde_support::hex_or_decimal_u128"))]
+        m_den: u128,
+    },
 }
 
 /// Config for the curve:
@@ -15,20 +102,295 @@ pub enum CurveError {
 /// - sell_amount: tokens sold over the bonding curve sellable_tokens
 /// - vt: virtual token reserves vt
 /// - mc_target_sats: desired final fully diluted market cap (in sats)
+/// - kind: pricing shape (see `CurveKind`)
+/// - fee_bps: pool fee, in basis points, taken out of `sats_in`/`sats_out` on every trade
+/// - protocol_fee_bps: optional extra protocol cut, in basis points, taken the same way
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CurveConfig {
-    pub total_supply: u128,   // total_supply
-    pub sell_amount: u128,    // sellable_tokens
-    pub vt: u128,             // vt
+    #[cfg_attr(feature = "serde", serde(with = "serde_support::hex_or_decimal_u128"))]
+    pub total_supply: u128, // total_supply
+    #[cfg_attr(feature = "serde", serde(with = "serde_support::hex_or_decimal_u128"))]
+    pub sell_amount: u128, // sellable_tokens
+    #[cfg_attr(feature = "serde", serde(with = "serde_support::hex_or_decimal_u128"))]
+    pub vt: u128, // vt
+    #[cfg_attr(feature = "serde", serde(with = "serde_support::hex_or_decimal_u128"))]
     pub mc_target_sats: u128, // final FDV target in sats
+    pub kind: CurveKind,
+    #[cfg_attr(feature = "serde", serde(with = "serde_support::hex_or_decimal_u128"))]
+    pub fee_bps: u128,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "serde_support::hex_or_decimal_u128_opt")
+    )]
+    pub protocol_fee_bps: Option<u128>,
 }
 
 /// sellable_tokensnapshot of the curve at a given step.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CurveSnapshot {
+    #[cfg_attr(feature = "serde", serde(with = "serde_support::hex_or_decimal_u128"))]
     pub step: u128, // how many tokens have been sold along the curve
-    pub x: u128,    // sats-side conceptual reserves
-    pub y: u128,    // token-side reserves (vt + remaining real)
+    #[cfg_attr(feature = "serde", serde(with = "serde_support::hex_or_decimal_u128"))]
+    pub x: u128, // sats-side conceptual reserves
+    #[cfg_attr(feature = "serde", serde(with = "serde_support::hex_or_decimal_u128"))]
+    pub y: u128, // token-side reserves (vt + remaining real)
+}
+
+/// Inputs needed to seed a real constant-product AMM pool once the bonding
+/// curve sale finishes, at the curve's terminal spot price. `reserve_sats`/
+/// `reserve_tokens` is the matched pair that reproduces
+/// `terminal_price_num / terminal_price_den` using only real reserves (the
+/// leftover, non-sellable tokens), with no virtual reserves involved.
+#[derive(Debug, Clone, Copy)]
+pub struct Graduation {
+    pub total_raise_sats: u128,
+    pub leftover_tokens: u128,
+    pub terminal_price_num: u128,
+    pub terminal_price_den: u128,
+    pub reserve_sats: u128,
+    pub reserve_tokens: u128,
+}
+
+/// A single leg of a `simulate_trades` batch: either a buy (sats in) or a
+/// sell (tokens in).
+#[derive(Debug, Clone, Copy)]
+pub enum Trade {
+    Buy(u128),
+    Sell(u128),
+}
+
+/// Outcome of a single `Trade`, tagged the same way as its input.
+#[derive(Debug, Clone, Copy)]
+pub enum TradeResult {
+    Buy {
+        start_step: u128,
+        tokens_out: u128,
+        fee_sats: u128,
+        protocol_fee_sats: u128,
+    },
+    Sell {
+        start_step: u128,
+        sats_out: u128,
+        fee_sats: u128,
+        protocol_fee_sats: u128,
+    },
+}
+
+/// Outcome of `Curve::mint`, with the fee split out of `sats_in`.
+#[derive(Debug, Clone, Copy)]
+pub struct MintExecution {
+    pub new_step: u128,
+    pub tokens_out: u128,
+    pub fee_sats: u128,
+    pub protocol_fee_sats: u128,
+}
+
+/// Outcome of `Curve::burn`, with the fee split out of `sats_out`.
+#[derive(Debug, Clone, Copy)]
+pub struct BurnExecution {
+    pub new_step: u128,
+    pub sats_out: u128,
+    pub fee_sats: u128,
+    pub protocol_fee_sats: u128,
+}
+
+/// Running totals of fees collected across a batch (see `simulate_mints`/
+/// `simulate_trades`).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FeeTotals {
+    pub fee_sats: u128,
+    pub protocol_fee_sats: u128,
+}
+
+/// Integer square root via Newton's method, used to invert the `Linear`
+/// curve's trapezoid formula. Floors, as all curve math does.
+fn isqrt_u128(n: u128) -> u128 {
+    if n == 0 {
+        return 0;
+    }
+    let mut x = n;
+    let mut y = x.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+    x
+}
+
+/// Minimal 256-bit unsigned integer: just the handful of operations `Fixed`
+/// needs (an exact 128x128 product, and a 256-bit-by-128-bit division).
+/// `hi` is the more significant limb, so field order also gives the correct
+/// derived `Ord`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct U256 {
+    hi: u128,
+    lo: u128,
+}
+
+impl U256 {
+    const ZERO: U256 = U256 { hi: 0, lo: 0 };
+
+    fn from_u128(v: u128) -> Self {
+        U256 { hi: 0, lo: v }
+    }
+
+    /// Exact product of two u128 values, via schoolbook multiplication over
+    /// 64-bit limbs so no partial product can overflow.
+    fn mul_u128(a: u128, b: u128) -> Self {
+        let a_limbs = [(a & u64::MAX as u128) as u64, (a >> 64) as u64];
+        let b_limbs = [(b & u64::MAX as u128) as u64, (b >> 64) as u64];
+        let mut limbs = [0u64; 4];
+
+        for (i, &ai) in a_limbs.iter().enumerate() {
+            let mut carry: u128 = 0;
+            for (j, &bj) in b_limbs.iter().enumerate() {
+                let idx = i + j;
+                let sum = (ai as u128) * (bj as u128) + limbs[idx] as u128 + carry;
+                limbs[idx] = sum as u64;
+                carry = sum >> 64;
+            }
+            let mut k = i + b_limbs.len();
+            while carry > 0 {
+                let sum = limbs[k] as u128 + carry;
+                limbs[k] = sum as u64;
+                carry = sum >> 64;
+                k += 1;
+            }
+        }
+
+        let lo = (limbs[0] as u128) | ((limbs[1] as u128) << 64);
+        let hi = (limbs[2] as u128) | ((limbs[3] as u128) << 64);
+        U256 { hi, lo }
+    }
+
+    fn checked_add(self, other: U256) -> Option<U256> {
+        let (lo, carry) = self.lo.overflowing_add(other.lo);
+        let (hi, overflow1) = self.hi.overflowing_add(other.hi);
+        let (hi, overflow2) = hi.overflowing_add(carry as u128);
+        if overflow1 || overflow2 {
+            None
+        } else {
+            Some(U256 { hi, lo })
+        }
+    }
+
+    fn checked_sub(self, other: U256) -> Option<U256> {
+        let (lo, borrow) = self.lo.overflowing_sub(other.lo);
+        let (hi, underflow1) = self.hi.overflowing_sub(other.hi);
+        let (hi, underflow2) = hi.overflowing_sub(borrow as u128);
+        if underflow1 || underflow2 {
+            None
+        } else {
+            Some(U256 { hi, lo })
+        }
+    }
+
+    fn shl1(self) -> Self {
+        U256 {
+            hi: (self.hi << 1) | (self.lo >> 127),
+            lo: self.lo << 1,
+        }
+    }
+
+    fn bit(&self, i: u32) -> bool {
+        if i >= 128 {
+            (self.hi >> (i - 128)) & 1 == 1
+        } else {
+            (self.lo >> i) & 1 == 1
+        }
+    }
+
+    fn set_bit(&mut self, i: u32) {
+        if i >= 128 {
+            self.hi |= 1 << (i - 128);
+        } else {
+            self.lo |= 1 << i;
+        }
+    }
+
+    /// Floor division by a u128 divisor, via bit-by-bit long division.
+    fn div_u128(self, divisor: u128) -> U256 {
+        debug_assert!(divisor != 0);
+        let divisor = U256::from_u128(divisor);
+        let mut remainder = U256::ZERO;
+        let mut quotient = U256::ZERO;
+
+        for i in (0..256u32).rev() {
+            remainder = remainder.shl1();
+            if self.bit(i) {
+                remainder.lo |= 1;
+            }
+            if remainder >= divisor {
+                remainder = remainder
+                    .checked_sub(divisor)
+                    .expect("remainder >= divisor");
+                quotient.set_bit(i);
+            }
+        }
+
+        quotient
+    }
+}
+
+/// Unsigned 128.128 fixed-point value: `U256` storing `real_value * 2^128`,
+/// i.e. the high limb is the integer part and the low limb is the
+/// fractional part. Used internally so a chain of curve math (spot price,
+/// market cap) only rounds down to an integer once, at the very end,
+/// instead of truncating at every intermediate division.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Fixed(U256);
+
+impl Fixed {
+    pub const ZERO: Fixed = Fixed(U256::ZERO);
+
+    /// `num / den` as a 128.128 fixed-point value, floor-rounded at the
+    /// `2^-128` unit (effectively exact for any sats/token-scale ratio).
+    pub fn from_ratio(num: u128, den: u128) -> Result<Self, CurveError> {
+        if den == 0 {
+            return Err(CurveError::InvalidConfig);
+        }
+        // num << 128, exact: the high limb of a 256-bit integer *is* num * 2^128.
+        let scaled = U256 { hi: num, lo: 0 };
+        Ok(Fixed(scaled.div_u128(den)))
+    }
+
+    /// Multiplies by a plain integer and floors back down to an integer in
+    /// one step, rounding toward the pool the same way the rest of this
+    /// module does.
+    pub fn mul_floor_u128(self, rhs: u128) -> Result<u128, CurveError> {
+        let hi_term = U256::mul_u128(self.0.hi, rhs);
+        let lo_term = U256::mul_u128(self.0.lo, rhs);
+        let combined = hi_term
+            .checked_add(U256::from_u128(lo_term.hi))
+            .ok_or(CurveError::InvalidConfig)?;
+        if combined.hi != 0 {
+            return Err(CurveError::InvalidConfig);
+        }
+        Ok(combined.lo)
+    }
+
+    /// Drops the fractional `2^-128` part, rounding down.
+    pub fn floor_to_u128(self) -> u128 {
+        self.0.hi
+    }
+
+    /// Formats the value to `decimals` fixed decimal places, rounding down.
+    pub fn to_decimal_string(self, decimals: u32) -> String {
+        let integer_part = self.0.hi;
+        if decimals == 0 {
+            return integer_part.to_string();
+        }
+        let pow10 = 10u128.saturating_pow(decimals);
+        // frac_digits = floor(fractional_part * 10^decimals), via the high
+        // limb of the exact 256-bit product (equivalent to `>> 128`).
+        let frac_digits = U256::mul_u128(self.0.lo, pow10).hi;
+        format!(
+            "{integer_part}.{frac_digits:0width$}",
+            width = decimals as usize
+        )
+    }
 }
 
 impl CurveSnapshot {
@@ -55,62 +417,136 @@ impl CurveSnapshot {
 ///   MC_final_sats ≈ (X0 * Y0 / vt^2) * total_supply
 ///   => X0 ≈ mc_target_sats * vt^2 / (Y0 * total_supply)
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Curve {
     // Immutable config
+    #[cfg_attr(feature = "serde", serde(with = "serde_support::hex_or_decimal_u128"))]
     pub total_supply: u128, // total_supply
-    pub sell_amount: u128,  // sellable_tokens
-    pub vt: u128,           // vt
+    #[cfg_attr(feature = "serde", serde(with = "serde_support::hex_or_decimal_u128"))]
+    pub sell_amount: u128, // sellable_tokens
+    #[cfg_attr(feature = "serde", serde(with = "serde_support::hex_or_decimal_u128"))]
+    pub vt: u128, // vt
+    pub kind: CurveKind,
+    #[cfg_attr(feature = "serde", serde(with = "serde_support::hex_or_decimal_u128"))]
+    pub fee_bps: u128,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "serde_support::hex_or_decimal_u128_opt")
+    )]
+    pub protocol_fee_bps: Option<u128>,
 
-    // Derived
+    // Derived. y0 is set for every kind; x0/k are ConstantProduct only and
+    // left as 0 for other kinds.
+    // Serialized too, so a rehydrated Curve never needs to re-derive them.
+    #[cfg_attr(feature = "serde", serde(with = "serde_support::hex_or_decimal_u128"))]
     pub y0: u128, // Y0 = vt + sellable_tokens
+    #[cfg_attr(feature = "serde", serde(with = "serde_support::hex_or_decimal_u128"))]
     pub x0: u128, // X0 (conceptual sats-side reserve)
-    pub k: u128,  // invariant: k = X0 * Y0
+    #[cfg_attr(feature = "serde", serde(with = "serde_support::hex_or_decimal_u128"))]
+    pub k: u128, // invariant: k = X0 * Y0
 }
 
+const FEE_BPS_DENOMINATOR: u128 = 10_000;
+
 impl Curve {
-    /// Construct from FDV target.
+    /// Construct from FDV target (`ConstantProduct`) or from the shape's own
+    /// parameters (`Flat`, `Linear`).
     pub fn new(cfg: CurveConfig) -> Result<Self, CurveError> {
         let total_supply = cfg.total_supply;
         let sellable_tokens = cfg.sell_amount;
         let vt = cfg.vt;
         let mc = cfg.mc_target_sats;
 
-        if total_supply == 0 || sellable_tokens == 0 || vt == 0 || mc == 0 {
+        if total_supply == 0 || sellable_tokens == 0 {
             return Err(CurveError::InvalidConfig);
         }
 
-        // Y0 = vt + sellable_tokens
+        let total_fee_bps = cfg
+            .fee_bps
+            .checked_add(cfg.protocol_fee_bps.unwrap_or(0))
+            .ok_or(CurveError::InvalidConfig)?;
+        if total_fee_bps >= FEE_BPS_DENOMINATOR {
+            return Err(CurveError::InvalidConfig);
+        }
+
+        // Y0 = vt + sellable_tokens (token-side bookkeeping shared by every kind)
         let y0 = vt
             .checked_add(sellable_tokens)
             .ok_or(CurveError::InvalidConfig)?;
 
-        // X0 ≈ mc_target_sats * vt^2 / (Y0 * total_supply)
-        let vt_sq: u128 = vt.checked_mul(vt).ok_or(CurveError::InvalidConfig)?;
-        let num = mc.checked_mul(vt_sq).ok_or(CurveError::InvalidConfig)?;
-        let den = y0
-            .checked_mul(total_supply)
-            .ok_or(CurveError::InvalidConfig)?;
-        if den == 0 {
-            return Err(CurveError::InvalidConfig);
-        }
+        let (x0, k) = match cfg.kind {
+            CurveKind::ConstantProduct => {
+                if vt == 0 || mc == 0 {
+                    return Err(CurveError::InvalidConfig);
+                }
 
-        let x0 = num.saturating_div(den);
-        if x0 == 0 {
-            return Err(CurveError::InvalidConfig);
-        }
+                // X0 ≈ mc_target_sats * vt^2 / (Y0 * total_supply)
+                let vt_sq: u128 = vt.checked_mul(vt).ok_or(CurveError::InvalidConfig)?;
+                let num = mc.checked_mul(vt_sq).ok_or(CurveError::InvalidConfig)?;
+                let den = y0
+                    .checked_mul(total_supply)
+                    .ok_or(CurveError::InvalidConfig)?;
+                if den == 0 {
+                    return Err(CurveError::InvalidConfig);
+                }
+
+                let x0 = num.saturating_div(den);
+                if x0 == 0 {
+                    return Err(CurveError::InvalidConfig);
+                }
 
-        let k = x0.checked_mul(y0).ok_or(CurveError::InvalidConfig)?;
+                let k = x0.checked_mul(y0).ok_or(CurveError::InvalidConfig)?;
+                (x0, k)
+            }
+            CurveKind::Flat {
+                price_num,
+                price_den,
+            } => {
+                if price_num == 0 || price_den == 0 {
+                    return Err(CurveError::InvalidConfig);
+                }
+                (0, 0)
+            }
+            CurveKind::Linear { m_num, m_den } => {
+                if m_num == 0 || m_den == 0 {
+                    return Err(CurveError::InvalidConfig);
+                }
+                (0, 0)
+            }
+        };
 
         Ok(Self {
             total_supply,
             sell_amount: sellable_tokens,
             vt,
+            kind: cfg.kind,
+            fee_bps: cfg.fee_bps,
+            protocol_fee_bps: cfg.protocol_fee_bps,
             y0,
             x0,
             k,
         })
     }
 
+    /// Splits a gross sats amount into (net, fee_sats, protocol_fee_sats),
+    /// rounding both fees down so the pool/protocol never over-collects.
+    fn split_fee(&self, gross: u128) -> Result<(u128, u128, u128), CurveError> {
+        let fee_sats = gross
+            .checked_mul(self.fee_bps)
+            .ok_or(CurveError::InvalidConfig)?
+            / FEE_BPS_DENOMINATOR;
+        let protocol_fee_sats = match self.protocol_fee_bps {
+            Some(bps) => {
+                gross.checked_mul(bps).ok_or(CurveError::InvalidConfig)? / FEE_BPS_DENOMINATOR
+            }
+            None => 0,
+        };
+        let net = gross
+            .saturating_sub(fee_sats)
+            .saturating_sub(protocol_fee_sats);
+        Ok((net, fee_sats, protocol_fee_sats))
+    }
+
     /// Max step (i.e. sellable_tokens).
     #[inline]
     pub fn max_step(&self) -> u128 {
@@ -135,38 +571,266 @@ impl Curve {
 
     /// Internal: X = floor(k / Y)
     fn x_from_y(&self, y: u128) -> u128 {
-        self.k / y
+        Fixed::from_ratio(self.k, y)
+            .expect("y is never zero")
+            .floor_to_u128()
     }
 
-    /// Get the curve state (X, Y, step) at a given step.
+    /// Get the curve state (X, Y, step) at a given step. `x`/`y` are always a
+    /// fraction whose ratio is the spot price in sats per token base unit,
+    /// even for the non-reserve-based shapes.
     pub fn snapshot(&self, step: u128) -> Result<CurveSnapshot, CurveError> {
         let y = self.y_at(step)?;
-        let x = self.x_from_y(y);
+        let (x, y) = match self.kind {
+            CurveKind::ConstantProduct => (self.x_from_y(y), y),
+            CurveKind::Flat {
+                price_num,
+                price_den,
+            } => (price_num, price_den),
+            CurveKind::Linear { m_num, m_den } => (
+                m_num.checked_mul(step).ok_or(CurveError::InvalidConfig)?,
+                m_den,
+            ),
+        };
         Ok(CurveSnapshot { step, x, y })
     }
 
+    /// Cumulative sats raised selling from step `0` up to `step`, i.e. the
+    /// fee-less quote `mint` would settle against if called once for the
+    /// whole range in a single shot.
+    pub fn cumulative_quote_to_step(&self, step: u128) -> Result<u128, CurveError> {
+        if step > self.sell_amount {
+            return Err(CurveError::OutOfRange);
+        }
+
+        match self.kind {
+            CurveKind::ConstantProduct => {
+                let y = self.y_at(step)?;
+                let x = self.x_from_y(y);
+                Ok(x.saturating_sub(self.x0))
+            }
+            CurveKind::Flat {
+                price_num,
+                price_den,
+            } => {
+                let numerator = step
+                    .checked_mul(price_num)
+                    .ok_or(CurveError::InvalidConfig)?;
+                Ok(numerator / price_den)
+            }
+            CurveKind::Linear { m_num, m_den } => {
+                // Area under p(step) = m*step from 0 to step: m * step^2 / 2.
+                let sq = step.checked_mul(step).ok_or(CurveError::InvalidConfig)?;
+                let denominator = m_den.checked_mul(2).ok_or(CurveError::InvalidConfig)?;
+                let numerator = sq.checked_mul(m_num).ok_or(CurveError::InvalidConfig)?;
+                Ok(numerator / denominator)
+            }
+        }
+    }
+
+    /// Fee-less quote: tokens `quote_in` sats would buy at `step`, without
+    /// actually executing a trade (no fee split, no state change).
+    pub fn asset_out_given_quote_in(&self, step: u128, quote_in: u128) -> Result<u128, CurveError> {
+        if quote_in == 0 {
+            return Err(CurveError::ZeroInput);
+        }
+
+        let (_, tokens_out) = match self.kind {
+            CurveKind::ConstantProduct => self.mint_constant_product(step, quote_in),
+            CurveKind::Flat {
+                price_num,
+                price_den,
+            } => self.mint_flat(step, quote_in, price_num, price_den),
+            CurveKind::Linear { m_num, m_den } => self.mint_linear(step, quote_in, m_num, m_den),
+        }?;
+
+        Ok(tokens_out)
+    }
+
+    /// Fee-less quote: sats needed to buy `asset_out` tokens at `step`,
+    /// without actually executing a trade. The pricing formula is
+    /// monotonically non-decreasing in the sats spent, so the minimal
+    /// quote is found by binary search the same way `buy_exact_out` finds
+    /// its (fee-aware, budget-capped) equivalent.
+    pub fn quote_in_given_asset_out(
+        &self,
+        step: u128,
+        asset_out: u128,
+    ) -> Result<u128, CurveError> {
+        if asset_out == 0 {
+            return Err(CurveError::ZeroInput);
+        }
+        let remaining = self.sell_amount.saturating_sub(step);
+        if asset_out > remaining {
+            return Err(CurveError::ExceedsPool);
+        }
+
+        // `asset_out_given_quote_in` errors with `ExceedsPool` once a quote
+        // would buy more than `remaining` -- same as `mint` -- so, the same
+        // way `buy_exact_out`'s `achievable` does, treat that as "bought the
+        // full remaining amount" rather than "bought nothing".
+        let achievable = |quote_in: u128| match self.asset_out_given_quote_in(step, quote_in) {
+            Ok(tokens) => tokens,
+            Err(CurveError::ExceedsPool) => remaining,
+            Err(_) => 0,
+        };
+
+        // Starting upper bound: the cumulative cost of the whole remaining
+        // window. `cumulative_quote_to_step` floors the cost of reaching a
+        // step, which can undershoot the sats actually required to mint
+        // `asset_out` tokens (`asset_out_given_quote_in` floors the other
+        // way), so bump it until it's verified sufficient before bisecting.
+        let mut hi = self
+            .cumulative_quote_to_step(self.sell_amount)?
+            .saturating_sub(self.cumulative_quote_to_step(step)?)
+            .max(1);
+        while achievable(hi) < asset_out {
+            hi = hi.checked_mul(2).ok_or(CurveError::InvalidConfig)?;
+        }
+
+        let mut lo = 1u128;
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if achievable(mid) >= asset_out {
+                hi = mid;
+            } else {
+                lo = mid + 1;
+            }
+        }
+
+        Ok(hi)
+    }
+
     /// Buy tokens with sats at a given step.
     ///
     /// Inputs:
     ///   - step: current step (0..sellable_tokens)
-    ///   - sats_in: sats the user pays now
+    ///   - sats_in: sats the user pays now, before fees
     ///
-    /// Returns:
-    ///   - new_step: updated step after purchase
-    ///   - tokens_out: tokens received
-    pub fn mint(&self, step: u128, sats_in: u128) -> Result<(u128, u128), CurveError> {
+    /// The pool fee (and optional protocol fee) is deducted from `sats_in`
+    /// before it ever reaches the curve, so the fee never changes the spot
+    /// price `mint` would have quoted on the net amount.
+    ///
+    /// Rounding always favors the pool (`Y' = ceil(k / x2)`, so tokens out
+    /// round down), so a buy immediately followed by a sell of the same size
+    /// can never mint free sats: see `burn` below.
+    pub fn mint(&self, step: u128, sats_in: u128) -> Result<MintExecution, CurveError> {
         if sats_in == 0 {
             return Err(CurveError::ZeroInput);
         }
 
+        let (net_sats_in, fee_sats, protocol_fee_sats) = self.split_fee(sats_in)?;
+
+        let (new_step, tokens_out) = match self.kind {
+            CurveKind::ConstantProduct => self.mint_constant_product(step, net_sats_in),
+            CurveKind::Flat {
+                price_num,
+                price_den,
+            } => self.mint_flat(step, net_sats_in, price_num, price_den),
+            CurveKind::Linear { m_num, m_den } => self.mint_linear(step, net_sats_in, m_num, m_den),
+        }?;
+
+        Ok(MintExecution {
+            new_step,
+            tokens_out,
+            fee_sats,
+            protocol_fee_sats,
+        })
+    }
+
+    /// Like `mint`, but guards against the quote having gone stale between
+    /// being shown to a user and being executed: errors with
+    /// `CurveError::SlippageExceeded` instead of silently filling at a worse
+    /// price than the caller agreed to.
+    pub fn mint_checked(
+        &self,
+        step: u128,
+        sats_in: u128,
+        min_tokens_out: u128,
+    ) -> Result<MintExecution, CurveError> {
+        let execution = self.mint(step, sats_in)?;
+        if execution.tokens_out < min_tokens_out {
+            return Err(CurveError::SlippageExceeded);
+        }
+        Ok(execution)
+    }
+
+    /// The symmetric "exact output" guard: buys exactly (at least)
+    /// `tokens_out` tokens, erroring with `CurveError::SlippageExceeded` if
+    /// that would cost more than `max_sats_in`. `mint`'s `tokens_out` is
+    /// monotonically non-decreasing in `sats_in` for every `CurveKind`, so
+    /// the minimal sats_in that clears `tokens_out` is found by binary
+    /// search over the caller's own budget rather than inverting each
+    /// shape's formula by hand.
+    pub fn buy_exact_out(
+        &self,
+        step: u128,
+        tokens_out: u128,
+        max_sats_in: u128,
+    ) -> Result<MintExecution, CurveError> {
+        if tokens_out == 0 {
+            return Err(CurveError::ZeroInput);
+        }
+        let remaining = self.sell_amount.saturating_sub(step);
+        if tokens_out > remaining {
+            return Err(CurveError::ExceedsPool);
+        }
+
+        // `mint`'s tokens_out is monotonically non-decreasing in sats_in and
+        // errors with `ExceedsPool` instead of overcharging once it would
+        // buy more than `remaining` -- which, since we've already checked
+        // `tokens_out <= remaining` above, can only mean "plenty", so it's
+        // treated the same as achieving the full `remaining` amount.
+        let achievable = |sats_in: u128| match self.mint(step, sats_in) {
+            Ok(execution) => execution.tokens_out,
+            Err(CurveError::ExceedsPool) => remaining,
+            Err(_) => 0,
+        };
+
+        if achievable(max_sats_in) < tokens_out {
+            return Err(CurveError::SlippageExceeded);
+        }
+
+        let mut lo = 1u128;
+        let mut hi = max_sats_in;
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if achievable(mid) >= tokens_out {
+                hi = mid;
+            } else {
+                lo = mid + 1;
+            }
+        }
+
+        // `hi` was chosen using `achievable`'s remap, so the actual
+        // execution needs the same remap: re-calling raw `mint(step, hi)`
+        // can itself hit the `ExceedsPool` case achievable absorbed above,
+        // in which case the real fill is the whole remaining pool rather
+        // than an error.
+        match self.mint(step, hi) {
+            Ok(execution) => Ok(execution),
+            Err(CurveError::ExceedsPool) => {
+                let (_, fee_sats, protocol_fee_sats) = self.split_fee(hi)?;
+                Ok(MintExecution {
+                    new_step: self.sell_amount,
+                    tokens_out: remaining,
+                    fee_sats,
+                    protocol_fee_sats,
+                })
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn mint_constant_product(&self, step: u128, sats_in: u128) -> Result<(u128, u128), CurveError> {
         let y = self.y_at(step)?;
         let x = self.x_from_y(y);
 
         // New X'
         let x2 = x.checked_add(sats_in).ok_or(CurveError::InvalidConfig)?;
 
-        // New Y' = floor(k / X'), but never below vt (don't touch virtual)
-        let y_raw = self.k / x2;
+        // New Y' = ceil(k / X'), but never below vt (don't touch virtual)
+        let y_raw = self.k.div_ceil(x2);
         let y_prime = if y_raw < self.vt { self.vt } else { y_raw };
 
         // total_supplyokens out = Y - Y'
@@ -177,41 +841,331 @@ impl Curve {
         Ok((new_step, dy))
     }
 
+    /// Fixed price per token: `tokens_out = floor(sats_in * price_den / price_num)`.
+    /// Floor rounding favors the pool.
+    fn mint_flat(
+        &self,
+        step: u128,
+        sats_in: u128,
+        price_num: u128,
+        price_den: u128,
+    ) -> Result<(u128, u128), CurveError> {
+        let numerator = sats_in
+            .checked_mul(price_den)
+            .ok_or(CurveError::InvalidConfig)?;
+        let tokens_out = numerator / price_num;
+        if tokens_out > self.sell_amount.saturating_sub(step) {
+            return Err(CurveError::ExceedsPool);
+        }
+        let new_step = step.saturating_add(tokens_out);
+        Ok((new_step, new_step.saturating_sub(step)))
+    }
+
+    /// Spot price `p(step) = m * step` (`m = m_num/m_den`). The sats to buy
+    /// `dy` tokens from `step` is the trapezoid `m * dy * (2*step + dy) / 2`,
+    /// so given `sats_in` we invert with an integer square root:
+    /// `dy = floor(sqrt(step^2 + 2*sats_in*m_den/m_num) - step)`.
+    fn mint_linear(
+        &self,
+        step: u128,
+        sats_in: u128,
+        m_num: u128,
+        m_den: u128,
+    ) -> Result<(u128, u128), CurveError> {
+        let step_sq = step.checked_mul(step).ok_or(CurveError::InvalidConfig)?;
+        let term = sats_in
+            .checked_mul(2)
+            .and_then(|v| v.checked_mul(m_den))
+            .ok_or(CurveError::InvalidConfig)?
+            / m_num;
+        let inside = step_sq.checked_add(term).ok_or(CurveError::InvalidConfig)?;
+        let dy = isqrt_u128(inside).saturating_sub(step);
+        if dy > self.sell_amount.saturating_sub(step) {
+            return Err(CurveError::ExceedsPool);
+        }
+        let new_step = step.saturating_add(dy);
+        Ok((new_step, new_step.saturating_sub(step)))
+    }
+
+    /// Sell tokens back into the curve at a given step, inverting `mint`
+    /// along the same invariant.
+    ///
+    /// Inputs:
+    ///   - step: current step (0..sellable_tokens)
+    ///   - tokens_in: tokens the user sells back now
+    ///
+    /// Returns:
+    ///   - new_step: updated step after the sale
+    ///   - sats_out: sats received, after fees
+    ///
+    /// The pool fee (and optional protocol fee) is deducted from the gross
+    /// `sats_out` the curve would otherwise pay, the same way `mint` deducts
+    /// it from `sats_in`.
+    ///
+    /// `Y' = Y + tokens_in` is capped at `y0` so `step` can never go below
+    /// zero, and `X' = floor(k / Y')` as in `snapshot`; since `mint` rounds
+    /// its `Y'` up, buy-then-sell can never mint free sats out of the pool.
+    pub fn burn(&self, step: u128, tokens_in: u128) -> Result<BurnExecution, CurveError> {
+        if tokens_in == 0 {
+            return Err(CurveError::ZeroInput);
+        }
+
+        let (new_step, gross_sats_out) = match self.kind {
+            CurveKind::ConstantProduct => self.burn_constant_product(step, tokens_in),
+            CurveKind::Flat {
+                price_num,
+                price_den,
+            } => self.burn_flat(step, tokens_in, price_num, price_den),
+            CurveKind::Linear { m_num, m_den } => self.burn_linear(step, tokens_in, m_num, m_den),
+        }?;
+
+        let (sats_out, fee_sats, protocol_fee_sats) = self.split_fee(gross_sats_out)?;
+
+        Ok(BurnExecution {
+            new_step,
+            sats_out,
+            fee_sats,
+            protocol_fee_sats,
+        })
+    }
+
+    fn burn_constant_product(
+        &self,
+        step: u128,
+        tokens_in: u128,
+    ) -> Result<(u128, u128), CurveError> {
+        let y = self.y_at(step)?;
+        let x = self.x_from_y(y);
+
+        // New Y', capped so step cannot go below 0 (Y' cannot exceed y0).
+        let y_prime = y.saturating_add(tokens_in).min(self.y0);
+
+        // New X' = floor(k / Y')
+        let x_prime = self.x_from_y(y_prime);
+
+        // Sats out = X - X'
+        let sats_out = x.saturating_sub(x_prime);
+
+        // New step = step - tokens_in, clamped by the same cap as Y'.
+        let new_step = step.saturating_sub(tokens_in);
+        Ok((new_step, sats_out))
+    }
+
+    /// Fixed price per token: `sats_out = floor(tokens_in * price_num / price_den)`.
+    fn burn_flat(
+        &self,
+        step: u128,
+        tokens_in: u128,
+        price_num: u128,
+        price_den: u128,
+    ) -> Result<(u128, u128), CurveError> {
+        let dy = tokens_in.min(step);
+        let new_step = step - dy;
+        let numerator = dy.checked_mul(price_num).ok_or(CurveError::InvalidConfig)?;
+        let sats_out = numerator / price_den;
+        Ok((new_step, sats_out))
+    }
+
+    /// Inverse of `mint_linear`'s trapezoid, evaluated directly since the
+    /// tokens sold back are already known: `sats_out = m * dy * (2*new_step + dy) / 2`.
+    fn burn_linear(
+        &self,
+        step: u128,
+        tokens_in: u128,
+        m_num: u128,
+        m_den: u128,
+    ) -> Result<(u128, u128), CurveError> {
+        let dy = tokens_in.min(step);
+        let new_step = step - dy;
+        let inner = new_step
+            .checked_mul(2)
+            .and_then(|v| v.checked_add(dy))
+            .ok_or(CurveError::InvalidConfig)?;
+        let numerator = dy
+            .checked_mul(inner)
+            .and_then(|v| v.checked_mul(m_num))
+            .ok_or(CurveError::InvalidConfig)?;
+        let denominator = m_den.checked_mul(2).ok_or(CurveError::InvalidConfig)?;
+        let sats_out = numerator / denominator;
+        Ok((new_step, sats_out))
+    }
+
     //Simulates the entire curve stack in wasm so node can cal this uber fast vroom vroom
-    pub fn simulate_mints(&self, mints: &[u128]) -> Result<Vec<(u128, u128)>, CurveError> {
+    ///
+    /// Returns each leg's execution alongside the lifetime fee totals
+    /// collected across the whole batch.
+    pub fn simulate_mints(
+        &self,
+        mints: &[u128],
+    ) -> Result<(Vec<MintExecution>, FeeTotals), CurveError> {
         let mut current_step: u128 = 0;
         let mut results = Vec::with_capacity(mints.len());
+        let mut totals = FeeTotals::default();
 
         for &mint in mints {
-            let (new_step, tokens_out) = self.mint(current_step, mint)?;
-            results.push((current_step, tokens_out));
-            current_step = new_step;
+            let execution = self.mint(current_step, mint)?;
+            totals.fee_sats += execution.fee_sats;
+            totals.protocol_fee_sats += execution.protocol_fee_sats;
+            current_step = execution.new_step;
+            results.push(execution);
         }
 
-        Ok(results)
+        Ok((results, totals))
     }
 
-    /// Helper: total sats raised if we sell the full window [0 -> sellable_tokens].
-    /// total_supplyhis is "curve-native": X_final - X0, where X_final = floor(k / vt).
-    pub fn total_raise_sats(&self) -> u128 {
-        let x_final = self.k / self.vt;
-        x_final.saturating_sub(self.x0)
+    /// Like `simulate_mints`, but each leg carries its own `min_tokens_out`
+    /// guard and a leg that would slip reports its own `Err` instead of
+    /// aborting the whole batch -- useful for previewing a batch of
+    /// limit-style buy orders before submission. A reverted leg leaves
+    /// `current_step` untouched for the next leg and isn't counted in the
+    /// fee totals.
+    pub fn simulate_mints_checked(
+        &self,
+        mints: &[(u128, u128)],
+    ) -> (Vec<Result<MintExecution, CurveError>>, FeeTotals) {
+        let mut current_step: u128 = 0;
+        let mut results = Vec::with_capacity(mints.len());
+        let mut totals = FeeTotals::default();
+
+        for &(sats_in, min_tokens_out) in mints {
+            match self.mint_checked(current_step, sats_in, min_tokens_out) {
+                Ok(execution) => {
+                    totals.fee_sats += execution.fee_sats;
+                    totals.protocol_fee_sats += execution.protocol_fee_sats;
+                    current_step = execution.new_step;
+                    results.push(Ok(execution));
+                }
+                Err(err) => results.push(Err(err)),
+            }
+        }
+
+        (results, totals)
+    }
+
+    /// Simulates a mixed batch of buys and sells in order, so a trading UI
+    /// can round-trip positions (buy then sell, or the reverse) and see the
+    /// step evolve leg by leg. Returns the lifetime fee totals collected
+    /// across the whole batch alongside each leg's result.
+    pub fn simulate_trades(
+        &self,
+        trades: &[Trade],
+    ) -> Result<(Vec<TradeResult>, FeeTotals), CurveError> {
+        let mut current_step: u128 = 0;
+        let mut results = Vec::with_capacity(trades.len());
+        let mut totals = FeeTotals::default();
+
+        for &trade in trades {
+            match trade {
+                Trade::Buy(sats_in) => {
+                    let execution = self.mint(current_step, sats_in)?;
+                    totals.fee_sats += execution.fee_sats;
+                    totals.protocol_fee_sats += execution.protocol_fee_sats;
+                    results.push(TradeResult::Buy {
+                        start_step: current_step,
+                        tokens_out: execution.tokens_out,
+                        fee_sats: execution.fee_sats,
+                        protocol_fee_sats: execution.protocol_fee_sats,
+                    });
+                    current_step = execution.new_step;
+                }
+                Trade::Sell(tokens_in) => {
+                    let execution = self.burn(current_step, tokens_in)?;
+                    totals.fee_sats += execution.fee_sats;
+                    totals.protocol_fee_sats += execution.protocol_fee_sats;
+                    results.push(TradeResult::Sell {
+                        start_step: current_step,
+                        sats_out: execution.sats_out,
+                        fee_sats: execution.fee_sats,
+                        protocol_fee_sats: execution.protocol_fee_sats,
+                    });
+                    current_step = execution.new_step;
+                }
+            }
+        }
+
+        Ok((results, totals))
     }
 
     /// Helper: total sats raised if we sell the full window [0 -> sellable_tokens].
     /// total_supplyhis is "curve-native": X_final - X0, where X_final = floor(k / vt).
-    pub fn final_mc_sats(&self) -> Result<u128, CurveError> {
-        let vt_sq = self
-            .vt
-            .checked_mul(self.vt)
-            .ok_or(CurveError::InvalidConfig)?;
-        let p_final = self.k / vt_sq;
+    pub fn total_raise_sats(&self) -> Result<u128, CurveError> {
+        match self.kind {
+            CurveKind::ConstantProduct => {
+                let x_final = self.k / self.vt;
+                Ok(x_final.saturating_sub(self.x0))
+            }
+            CurveKind::Flat {
+                price_num,
+                price_den,
+            } => {
+                let numerator = self
+                    .sell_amount
+                    .checked_mul(price_num)
+                    .ok_or(CurveError::InvalidConfig)?;
+                Ok(numerator / price_den)
+            }
+            CurveKind::Linear { m_num, m_den } => {
+                // Area under p(step) = m*step from 0 to sell_amount: m * sell_amount^2 / 2.
+                let sq = self
+                    .sell_amount
+                    .checked_mul(self.sell_amount)
+                    .ok_or(CurveError::InvalidConfig)?;
+                let numerator = sq.checked_mul(m_num).ok_or(CurveError::InvalidConfig)?;
+                let denominator = m_den.checked_mul(2).ok_or(CurveError::InvalidConfig)?;
+                Ok(numerator / denominator)
+            }
+        }
+    }
 
-        Ok(p_final.saturating_mul(self.total_supply))
+    /// Helper: final fully-diluted market cap in sats, priced at the
+    /// terminal spot price of the curve.
+    pub fn final_mc_sats(&self) -> Result<u128, CurveError> {
+        match self.kind {
+            CurveKind::ConstantProduct => {
+                let vt_sq = self
+                    .vt
+                    .checked_mul(self.vt)
+                    .ok_or(CurveError::InvalidConfig)?;
+                // Route through `Fixed` so the division and the multiply by
+                // `total_supply` only round down once, at the end, instead
+                // of truncating `k / vt_sq` and then truncating again.
+                Fixed::from_ratio(self.k, vt_sq)?.mul_floor_u128(self.total_supply)
+            }
+            CurveKind::Flat {
+                price_num,
+                price_den,
+            } => {
+                let numerator = self
+                    .total_supply
+                    .checked_mul(price_num)
+                    .ok_or(CurveError::InvalidConfig)?;
+                Ok(numerator / price_den)
+            }
+            CurveKind::Linear { m_num, m_den } => {
+                let numerator = self
+                    .sell_amount
+                    .checked_mul(m_num)
+                    .ok_or(CurveError::InvalidConfig)?;
+                let p_final = numerator / m_den;
+                self.total_supply
+                    .checked_mul(p_final)
+                    .ok_or(CurveError::InvalidConfig)
+            }
+        }
     }
 
     pub fn progress_at_step(&self, step: u128) -> u128 {
-        step.saturating_mul(100u128) / self.total_supply
+        Fixed::from_ratio(step, self.total_supply)
+            .and_then(|f| f.mul_floor_u128(100))
+            .unwrap_or(0)
+    }
+
+    /// Spot price (sats per token base unit) at a given step, as a 128.128
+    /// fixed-point ratio floored at the `2^-128` unit — unlike `snapshot`'s raw `(x, y)` pair,
+    /// this is already reduced to a single comparable value.
+    pub fn spot_price(&self, step: u128) -> Result<Fixed, CurveError> {
+        let snap = self.snapshot(step)?;
+        Fixed::from_ratio(snap.x, snap.y)
     }
 
     pub fn avg_progess(&self, steps: &[u128]) -> u128 {
@@ -219,4 +1173,192 @@ impl Curve {
         let sum: u128 = steps.iter().copied().sum();
         product / sum
     }
+
+    /// Whether the sale has sold through its entire `sell_amount`.
+    pub fn is_graduated(&self, step: u128) -> bool {
+        step >= self.sell_amount
+    }
+
+    /// Computes the parameters needed to seed a real constant-product AMM
+    /// pool at the curve's terminal price, once the sale graduates. Valid
+    /// to call at any time (it always prices at `sell_amount`, not the
+    /// current step).
+    pub fn graduation(&self) -> Result<Graduation, CurveError> {
+        let terminal = self.snapshot(self.sell_amount)?;
+        let leftover_tokens = self
+            .total_supply
+            .checked_sub(self.sell_amount)
+            .ok_or(CurveError::InvalidConfig)?;
+        let terminal_price = Fixed::from_ratio(terminal.x, terminal.y)?;
+        let reserve_sats = terminal_price.mul_floor_u128(leftover_tokens)?;
+
+        Ok(Graduation {
+            total_raise_sats: self.total_raise_sats()?,
+            leftover_tokens,
+            terminal_price_num: terminal.x,
+            terminal_price_den: terminal.y,
+            reserve_sats,
+            reserve_tokens: leftover_tokens,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn curves() -> Vec<Curve> {
+        vec![
+            Curve::new(CurveConfig {
+                total_supply: 1_000_000,
+                sell_amount: 500_000,
+                vt: 1_000_000,
+                mc_target_sats: 1_000_000_000,
+                kind: CurveKind::ConstantProduct,
+                fee_bps: 100,
+                protocol_fee_bps: Some(50),
+            })
+            .unwrap(),
+            Curve::new(CurveConfig {
+                total_supply: 1_000,
+                sell_amount: 300,
+                vt: 0,
+                mc_target_sats: 0,
+                kind: CurveKind::Flat {
+                    price_num: 1,
+                    price_den: 5,
+                },
+                fee_bps: 100,
+                protocol_fee_bps: Some(25),
+            })
+            .unwrap(),
+            Curve::new(CurveConfig {
+                total_supply: 1_000,
+                sell_amount: 200,
+                vt: 0,
+                mc_target_sats: 0,
+                kind: CurveKind::Linear {
+                    m_num: 1,
+                    m_den: 1_000,
+                },
+                fee_bps: 50,
+                protocol_fee_bps: None,
+            })
+            .unwrap(),
+        ]
+    }
+
+    #[test]
+    fn mint_then_burn_never_creates_free_sats() {
+        for curve in curves() {
+            // A fee-less quote for a small slice of the pool is always
+            // affordable and always within range, regardless of shape/scale.
+            let sats_in = curve
+                .quote_in_given_asset_out(0, curve.sell_amount / 10)
+                .unwrap();
+            let mint = curve.mint(0, sats_in).unwrap();
+            let burn = curve.burn(mint.new_step, mint.tokens_out).unwrap();
+            assert!(
+                burn.sats_out <= sats_in,
+                "round trip minted free sats: put in {sats_in}, got back {}",
+                burn.sats_out
+            );
+        }
+    }
+
+    #[test]
+    fn buy_exact_out_fills_at_least_the_requested_amount() {
+        for curve in curves() {
+            let tokens_out = curve.sell_amount / 10;
+            // Budget generously above the fee-less quote so the fee split
+            // taken out of sats_in never trips SlippageExceeded.
+            let max_sats_in = curve.quote_in_given_asset_out(0, tokens_out).unwrap() * 10 + 10;
+            let execution = curve.buy_exact_out(0, tokens_out, max_sats_in).unwrap();
+            assert!(execution.tokens_out >= tokens_out);
+            assert!(execution.new_step <= curve.sell_amount);
+        }
+    }
+
+    #[test]
+    fn buy_exact_out_can_fill_the_entire_remaining_pool() {
+        // Regression: a Flat/Linear curve whose price ratio doesn't evenly
+        // divide the remaining pool must still be able to fill a request
+        // for (at least) all of it, instead of erroring with ExceedsPool.
+        let curve = Curve::new(CurveConfig {
+            total_supply: 100,
+            sell_amount: 20,
+            vt: 0,
+            mc_target_sats: 0,
+            kind: CurveKind::Flat {
+                price_num: 1,
+                price_den: 3,
+            },
+            fee_bps: 0,
+            protocol_fee_bps: None,
+        })
+        .unwrap();
+
+        let execution = curve.buy_exact_out(0, 20, 1_000_000).unwrap();
+        assert_eq!(execution.tokens_out, 20);
+        assert_eq!(execution.new_step, 20);
+    }
+
+    #[test]
+    fn quote_in_given_asset_out_is_achievable() {
+        for curve in curves() {
+            let asset_out = curve.sell_amount / 10;
+            let quote = curve.quote_in_given_asset_out(0, asset_out).unwrap();
+            let tokens = curve.asset_out_given_quote_in(0, quote).unwrap();
+            assert!(
+                tokens >= asset_out,
+                "quote {quote} only yields {tokens} tokens, wanted at least {asset_out}"
+            );
+        }
+    }
+
+    #[test]
+    fn quote_in_given_asset_out_covers_the_whole_remaining_pool() {
+        // Regression: cumulative_quote_to_step floors the cost of reaching a
+        // step, which can undershoot the sats actually needed to mint every
+        // last token in the window.
+        let curve = Curve::new(CurveConfig {
+            total_supply: 100,
+            sell_amount: 10,
+            vt: 0,
+            mc_target_sats: 0,
+            kind: CurveKind::Flat {
+                price_num: 1,
+                price_den: 3,
+            },
+            fee_bps: 0,
+            protocol_fee_bps: None,
+        })
+        .unwrap();
+
+        let quote = curve.quote_in_given_asset_out(0, 10).unwrap();
+        let achievable = match curve.asset_out_given_quote_in(0, quote) {
+            Ok(tokens) => tokens,
+            Err(CurveError::ExceedsPool) => curve.sell_amount,
+            Err(err) => panic!("unexpected error: {err:?}"),
+        };
+        assert!(achievable >= 10);
+    }
+
+    #[test]
+    fn fee_split_matches_configured_bps() {
+        for curve in curves() {
+            let sats_in = curve
+                .quote_in_given_asset_out(0, curve.sell_amount / 10)
+                .unwrap();
+            let execution = curve.mint(0, sats_in).unwrap();
+
+            let expected_fee = sats_in * curve.fee_bps / FEE_BPS_DENOMINATOR;
+            let expected_protocol_fee =
+                sats_in * curve.protocol_fee_bps.unwrap_or(0) / FEE_BPS_DENOMINATOR;
+
+            assert_eq!(execution.fee_sats, expected_fee);
+            assert_eq!(execution.protocol_fee_sats, expected_protocol_fee);
+            assert!(execution.fee_sats + execution.protocol_fee_sats <= sats_in);
+        }
+    }
 }
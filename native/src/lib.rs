@@ -1,6 +1,10 @@
 // src/lib.rs
 
 // Core curve math (no wasm, pure Rust).
+mod curve;
 mod wasm;
+pub use crate::curve::{
+    BurnExecution, Curve, CurveConfig, CurveError, CurveKind, CurveSnapshot, FeeTotals, Fixed,
+    Graduation, MintExecution, Trade, TradeResult,
+};
 pub use crate::wasm::{MintResult, WasmCurve, WasmCurveSnapshot};
-pub use pbcurve::{Curve, CurveConfig, CurveError, CurveSnapshot};
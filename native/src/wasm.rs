@@ -1,6 +1,6 @@
 // src/wasm.rs
 
-use crate::curve::{Curve, CurveConfig, CurveError};
+use crate::curve::{Curve, CurveConfig, CurveError, CurveKind, Graduation, Trade, TradeResult};
 use wasm_bindgen::prelude::*;
 
 impl CurveError {
@@ -14,6 +14,24 @@ fn parse_u128_dec(s: &str) -> Result<u128, JsValue> {
         .map_err(|_| JsError::new(&format!("Invalid u128 decimal: {s}")).into())
 }
 
+/// Parses the `kind`/`param_a`/`param_b` constructor arguments into a
+/// `CurveKind`. `param_a`/`param_b` are `price_num`/`price_den` for `"flat"`,
+/// `m_num`/`m_den` for `"linear"`, and ignored for `"constant_product"`.
+fn parse_curve_kind(kind: &str, param_a: &str, param_b: &str) -> Result<CurveKind, JsValue> {
+    match kind {
+        "constant_product" => Ok(CurveKind::ConstantProduct),
+        "flat" => Ok(CurveKind::Flat {
+            price_num: parse_u128_dec(param_a)?,
+            price_den: parse_u128_dec(param_b)?,
+        }),
+        "linear" => Ok(CurveKind::Linear {
+            m_num: parse_u128_dec(param_a)?,
+            m_den: parse_u128_dec(param_b)?,
+        }),
+        other => Err(JsError::new(&format!("Invalid curve kind: {other}")).into()),
+    }
+}
+
 #[wasm_bindgen]
 pub struct WasmCurve {
     inner: Curve,
@@ -28,29 +46,124 @@ pub struct WasmCurveSnapshot {
     pub y: String,
 }
 
+#[derive(Clone)]
 #[wasm_bindgen(getter_with_clone)]
 pub struct MintResult {
     pub start_step: String,
     pub tokens_out: String,
+    pub fee_sats: String,
+    pub protocol_fee_sats: String,
+}
+
+#[derive(Clone)]
+#[wasm_bindgen(getter_with_clone)]
+pub struct BurnResult {
+    pub new_step: String,
+    pub sats_out: String,
+    pub fee_sats: String,
+    pub protocol_fee_sats: String,
+}
+
+/// One leg of a `simulate_trades` batch result. `kind` is `"buy"` or
+/// `"sell"`; the other leg's field is left as `"0"`.
+#[derive(Clone)]
+#[wasm_bindgen(getter_with_clone)]
+pub struct TradeLegResult {
+    pub kind: String,
+    pub start_step: String,
+    pub tokens_out: String,
+    pub sats_out: String,
+    pub fee_sats: String,
+    pub protocol_fee_sats: String,
+}
+
+/// A batch of mint legs alongside the lifetime fee totals collected across it.
+#[wasm_bindgen(getter_with_clone)]
+pub struct MintBatchResult {
+    pub legs: Box<[MintResult]>,
+    pub total_fee_sats: String,
+    pub total_protocol_fee_sats: String,
+}
+
+/// One leg of a `simulate_mints_checked` batch. `ok` is `false` when the leg
+/// would have slipped past its `min_tokens_out`, in which case `error` holds
+/// the `CurveError` and the other numeric fields are left as `"0"`.
+#[derive(Clone)]
+#[wasm_bindgen(getter_with_clone)]
+pub struct CheckedMintLegResult {
+    pub ok: bool,
+    pub start_step: String,
+    pub tokens_out: String,
+    pub fee_sats: String,
+    pub protocol_fee_sats: String,
+    pub error: String,
+}
+
+/// A batch of checked-mint legs alongside the lifetime fee totals collected
+/// across only the legs that didn't revert.
+#[wasm_bindgen(getter_with_clone)]
+pub struct CheckedMintBatchResult {
+    pub legs: Box<[CheckedMintLegResult]>,
+    pub total_fee_sats: String,
+    pub total_protocol_fee_sats: String,
+}
+
+/// A batch of trade legs alongside the lifetime fee totals collected across it.
+#[wasm_bindgen(getter_with_clone)]
+pub struct TradeBatchResult {
+    pub legs: Box<[TradeLegResult]>,
+    pub total_fee_sats: String,
+    pub total_protocol_fee_sats: String,
+}
+
+/// Parameters to seed a real constant-product AMM pool once the bonding
+/// curve graduates.
+#[wasm_bindgen(getter_with_clone)]
+pub struct WasmGraduation {
+    pub total_raise_sats: String,
+    pub leftover_tokens: String,
+    pub terminal_price_num: String,
+    pub terminal_price_den: String,
+    pub reserve_sats: String,
+    pub reserve_tokens: String,
 }
 
 #[wasm_bindgen]
 impl WasmCurve {
     /// Constructor exposed to JS.
     ///
-    /// All params are decimal strings representing u128.
+    /// All numeric params are decimal strings representing u128. `kind` is
+    /// one of `"constant_product"`, `"flat"`, or `"linear"` (see
+    /// `CurveKind`); `param_a`/`param_b` hold that shape's extra
+    /// parameters and are ignored for `"constant_product"` (pass `"0"`).
+    /// `protocol_fee_bps` is an empty string for "no protocol fee".
     #[wasm_bindgen(constructor)]
+    #[allow(clippy::too_many_arguments)] // one decimal-string field per CurveConfig member; matches the JS constructor shape
     pub fn new(
         total_supply: String,
         sell_amount: String,
         vt: String,
         mc_target_sats: String,
+        kind: String,
+        param_a: String,
+        param_b: String,
+        fee_bps: String,
+        protocol_fee_bps: String,
     ) -> Result<WasmCurve, JsValue> {
+        let protocol_fee_bps = if protocol_fee_bps.is_empty() {
+            None
+        } else {
+            Some(parse_u128_dec(&protocol_fee_bps)?)
+        };
+
         let cfg = CurveConfig {
             total_supply: parse_u128_dec(&total_supply)?,
             sell_amount: parse_u128_dec(&sell_amount)?,
             vt: parse_u128_dec(&vt)?,
             mc_target_sats: parse_u128_dec(&mc_target_sats)?,
+            kind: parse_curve_kind(&kind, &param_a, &param_b)?,
+            fee_bps: parse_u128_dec(&fee_bps)?,
+            protocol_fee_bps,
         };
 
         let inner = Curve::new(cfg).map_err(|e| e.to_js())?;
@@ -70,8 +183,9 @@ impl WasmCurve {
     }
 
     /// Total raise in sats, as decimal string (u128).
-    pub fn total_raise_sats(&self) -> String {
-        self.inner.total_raise_sats().to_string()
+    pub fn total_raise_sats(&self) -> Result<String, JsValue> {
+        let v = self.inner.total_raise_sats().map_err(|e| e.to_js())?;
+        Ok(v.to_string())
     }
 
     /// Final MC in sats, as decimal string (u128).
@@ -86,6 +200,41 @@ impl WasmCurve {
         Ok(self.inner.progress_at_step(step_u).to_string())
     }
 
+    /// Spot price at a given step (sats per token base unit), formatted as a
+    /// fixed-point decimal string with the requested number of places.
+    pub fn price_decimal_string(&self, step: String, decimals: u32) -> Result<String, JsValue> {
+        let step_u = parse_u128_dec(&step)?;
+        let price = self.inner.spot_price(step_u).map_err(|e| e.to_js())?;
+        Ok(price.to_decimal_string(decimals))
+    }
+
+    /// Whether the sale has sold through its entire `sell_amount`.
+    pub fn is_graduated(&self, step: String) -> Result<bool, JsValue> {
+        let step_u = parse_u128_dec(&step)?;
+        Ok(self.inner.is_graduated(step_u))
+    }
+
+    /// AMM seeding parameters for once the bonding curve graduates.
+    pub fn graduation(&self) -> Result<WasmGraduation, JsValue> {
+        let Graduation {
+            total_raise_sats,
+            leftover_tokens,
+            terminal_price_num,
+            terminal_price_den,
+            reserve_sats,
+            reserve_tokens,
+        } = self.inner.graduation().map_err(|e| e.to_js())?;
+
+        Ok(WasmGraduation {
+            total_raise_sats: total_raise_sats.to_string(),
+            leftover_tokens: leftover_tokens.to_string(),
+            terminal_price_num: terminal_price_num.to_string(),
+            terminal_price_den: terminal_price_den.to_string(),
+            reserve_sats: reserve_sats.to_string(),
+            reserve_tokens: reserve_tokens.to_string(),
+        })
+    }
+
     /// Asset out (tokens) for a given quote-in amount at a specific step.
     pub fn asset_out_given_quote_in(
         &self,
@@ -126,25 +275,215 @@ impl WasmCurve {
         Ok(total.to_string())
     }
 
+    /// Sell tokens back into the curve at a given step (inverse of `mint`).
+    pub fn burn(&self, step: String, tokens_in: String) -> Result<BurnResult, JsValue> {
+        let step_u = parse_u128_dec(&step)?;
+        let tokens_u = parse_u128_dec(&tokens_in)?;
+        let execution = self.inner.burn(step_u, tokens_u).map_err(|e| e.to_js())?;
+
+        Ok(BurnResult {
+            new_step: execution.new_step.to_string(),
+            sats_out: execution.sats_out.to_string(),
+            fee_sats: execution.fee_sats.to_string(),
+            protocol_fee_sats: execution.protocol_fee_sats.to_string(),
+        })
+    }
+
+    /// Buy tokens with sats, erroring instead of filling if the quote has
+    /// slipped below `min_tokens_out` since it was shown to the caller.
+    pub fn mint_checked(
+        &self,
+        step: String,
+        sats_in: String,
+        min_tokens_out: String,
+    ) -> Result<MintResult, JsValue> {
+        let step_u = parse_u128_dec(&step)?;
+        let sats_u = parse_u128_dec(&sats_in)?;
+        let min_tokens_u = parse_u128_dec(&min_tokens_out)?;
+        let execution = self
+            .inner
+            .mint_checked(step_u, sats_u, min_tokens_u)
+            .map_err(|e| e.to_js())?;
+
+        Ok(MintResult {
+            start_step: step_u.to_string(),
+            tokens_out: execution.tokens_out.to_string(),
+            fee_sats: execution.fee_sats.to_string(),
+            protocol_fee_sats: execution.protocol_fee_sats.to_string(),
+        })
+    }
+
+    /// Buy exactly (at least) `tokens_out` tokens, erroring instead of
+    /// filling if that would cost more than `max_sats_in`.
+    pub fn buy_exact_out(
+        &self,
+        step: String,
+        tokens_out: String,
+        max_sats_in: String,
+    ) -> Result<MintResult, JsValue> {
+        let step_u = parse_u128_dec(&step)?;
+        let tokens_u = parse_u128_dec(&tokens_out)?;
+        let max_sats_u = parse_u128_dec(&max_sats_in)?;
+        let execution = self
+            .inner
+            .buy_exact_out(step_u, tokens_u, max_sats_u)
+            .map_err(|e| e.to_js())?;
+
+        Ok(MintResult {
+            start_step: step_u.to_string(),
+            tokens_out: execution.tokens_out.to_string(),
+            fee_sats: execution.fee_sats.to_string(),
+            protocol_fee_sats: execution.protocol_fee_sats.to_string(),
+        })
+    }
+
+    /// Simulate a tagged batch of buys and sells in order.
+    ///
+    /// `kinds` holds `"buy"` / `"sell"` tags and `amounts` holds the
+    /// matching decimal-string u128 amount (sats_in for a buy, tokens_in
+    /// for a sell) for each leg, so a trading UI can round-trip positions.
+    pub fn simulate_trades(
+        &self,
+        kinds: Vec<String>,
+        amounts: Vec<String>,
+    ) -> Result<TradeBatchResult, JsValue> {
+        if kinds.len() != amounts.len() {
+            return Err(JsError::new("kinds and amounts must be the same length").into());
+        }
+
+        let mut trades: Vec<Trade> = Vec::with_capacity(kinds.len());
+        for (kind, amount) in kinds.iter().zip(amounts.iter()) {
+            let amount_u = parse_u128_dec(amount)?;
+            match kind.as_str() {
+                "buy" => trades.push(Trade::Buy(amount_u)),
+                "sell" => trades.push(Trade::Sell(amount_u)),
+                other => return Err(JsError::new(&format!("Invalid trade kind: {other}")).into()),
+            }
+        }
+
+        let (res, totals) = self.inner.simulate_trades(&trades).map_err(|e| e.to_js())?;
+
+        let mut legs: Vec<TradeLegResult> = Vec::with_capacity(res.len());
+        for trade_result in res.into_iter() {
+            legs.push(match trade_result {
+                TradeResult::Buy {
+                    start_step,
+                    tokens_out,
+                    fee_sats,
+                    protocol_fee_sats,
+                } => TradeLegResult {
+                    kind: "buy".to_string(),
+                    start_step: start_step.to_string(),
+                    tokens_out: tokens_out.to_string(),
+                    sats_out: "0".to_string(),
+                    fee_sats: fee_sats.to_string(),
+                    protocol_fee_sats: protocol_fee_sats.to_string(),
+                },
+                TradeResult::Sell {
+                    start_step,
+                    sats_out,
+                    fee_sats,
+                    protocol_fee_sats,
+                } => TradeLegResult {
+                    kind: "sell".to_string(),
+                    start_step: start_step.to_string(),
+                    tokens_out: "0".to_string(),
+                    sats_out: sats_out.to_string(),
+                    fee_sats: fee_sats.to_string(),
+                    protocol_fee_sats: protocol_fee_sats.to_string(),
+                },
+            });
+        }
+
+        Ok(TradeBatchResult {
+            legs: legs.into_boxed_slice(),
+            total_fee_sats: totals.fee_sats.to_string(),
+            total_protocol_fee_sats: totals.protocol_fee_sats.to_string(),
+        })
+    }
+
     /// Simulate a batch of mints.
     ///
     /// `mints` is an array of decimal-string u128 sats_in values.
-    pub fn simulate_mints(&self, mints: Box<[String]>) -> Result<Box<[MintResult]>, JsValue> {
+    pub fn simulate_mints(&self, mints: Vec<String>) -> Result<MintBatchResult, JsValue> {
         let mut parsed: Vec<u128> = Vec::with_capacity(mints.len());
         for s in mints.iter() {
             parsed.push(parse_u128_dec(s)?);
         }
 
-        let res = self.inner.simulate_mints(&parsed).map_err(|e| e.to_js())?;
-        let mut out: Vec<MintResult> = Vec::with_capacity(res.len());
+        let (res, totals) = self.inner.simulate_mints(&parsed).map_err(|e| e.to_js())?;
+        let mut legs: Vec<MintResult> = Vec::with_capacity(res.len());
 
-        for (start_step, tokens_out) in res.into_iter() {
-            out.push(MintResult {
+        let mut start_step = 0u128;
+        for execution in res.into_iter() {
+            legs.push(MintResult {
                 start_step: start_step.to_string(),
-                tokens_out: tokens_out.to_string(),
+                tokens_out: execution.tokens_out.to_string(),
+                fee_sats: execution.fee_sats.to_string(),
+                protocol_fee_sats: execution.protocol_fee_sats.to_string(),
+            });
+            start_step = execution.new_step;
+        }
+
+        Ok(MintBatchResult {
+            legs: legs.into_boxed_slice(),
+            total_fee_sats: totals.fee_sats.to_string(),
+            total_protocol_fee_sats: totals.protocol_fee_sats.to_string(),
+        })
+    }
+
+    /// Simulate a batch of slippage-guarded mints, reporting which legs
+    /// would have reverted instead of aborting the whole batch.
+    ///
+    /// `mints` and `min_tokens_outs` are parallel arrays of decimal-string
+    /// u128 values: `sats_in` and that leg's `min_tokens_out`.
+    pub fn simulate_mints_checked(
+        &self,
+        mints: Vec<String>,
+        min_tokens_outs: Vec<String>,
+    ) -> Result<CheckedMintBatchResult, JsValue> {
+        if mints.len() != min_tokens_outs.len() {
+            return Err(JsError::new("mints and min_tokens_outs must be the same length").into());
+        }
+
+        let mut parsed: Vec<(u128, u128)> = Vec::with_capacity(mints.len());
+        for (sats_in, min_tokens_out) in mints.iter().zip(min_tokens_outs.iter()) {
+            parsed.push((parse_u128_dec(sats_in)?, parse_u128_dec(min_tokens_out)?));
+        }
+
+        let (res, totals) = self.inner.simulate_mints_checked(&parsed);
+        let mut legs: Vec<CheckedMintLegResult> = Vec::with_capacity(res.len());
+
+        let mut start_step = 0u128;
+        for result in res.into_iter() {
+            legs.push(match result {
+                Ok(execution) => {
+                    let leg = CheckedMintLegResult {
+                        ok: true,
+                        start_step: start_step.to_string(),
+                        tokens_out: execution.tokens_out.to_string(),
+                        fee_sats: execution.fee_sats.to_string(),
+                        protocol_fee_sats: execution.protocol_fee_sats.to_string(),
+                        error: String::new(),
+                    };
+                    start_step = execution.new_step;
+                    leg
+                }
+                Err(err) => CheckedMintLegResult {
+                    ok: false,
+                    start_step: start_step.to_string(),
+                    tokens_out: "0".to_string(),
+                    fee_sats: "0".to_string(),
+                    protocol_fee_sats: "0".to_string(),
+                    error: format!("{err:?}"),
+                },
             });
         }
 
-        Ok(out.into_boxed_slice())
+        Ok(CheckedMintBatchResult {
+            legs: legs.into_boxed_slice(),
+            total_fee_sats: totals.fee_sats.to_string(),
+            total_protocol_fee_sats: totals.protocol_fee_sats.to_string(),
+        })
     }
 }